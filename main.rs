@@ -1,10 +1,12 @@
-use std::collections::HashMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::error::Error;
 use csv::Reader;
+use rayon::prelude::*;
 
 #[allow(dead_code)]
-#[derive(Default)]
-struct Node {  
+#[derive(Default, Clone)]
+struct Node {
     food_insecurity_score: f64,
     census_tract: String,
     county: String,
@@ -107,40 +109,107 @@ fn create_nodes(data: Vec<Vec<String>>) -> HashMap<String, Node> {
     nodes
 } 
 
+// Builds the edge set for all tracts in O(n^2) instead of the O(n^3) you get
+// from re-scanning the HashMap's key iterator on every comparison, and
+// computes the pairwise comparisons across threads with rayon since they are
+// independent of one another.
 fn create_edges(nodes: &mut HashMap<String, Node>) {
-    for i in 0..nodes.len() {
-        let node_key = nodes.keys().nth(i).unwrap().to_owned();
-        let node = nodes.get(&node_key).unwrap();
-        let mut edges_to_add = Vec::new();
-
-        for j in (i + 1)..nodes.len() {
-            let other_node_key = nodes.keys().nth(j).unwrap().to_owned();
-            let other_node = nodes.get(&other_node_key).unwrap();
+    let keys: Vec<String> = nodes.keys().cloned().collect();
+    let index_of_key: HashMap<String, usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (key.clone(), i))
+        .collect();
+    let mut node_vec: Vec<Node> = vec![Node::default(); keys.len()];
+    for key in &keys {
+        node_vec[index_of_key[key]] = nodes[key].clone();
+    }
 
-            if should_add_edge(node, other_node) {
-                edges_to_add.push((node_key.clone(), other_node_key.clone()));
+    let edges_to_add: Vec<(usize, usize)> = (0..node_vec.len())
+        .into_par_iter()
+        .flat_map(|i| {
+            let mut local_edges = Vec::new();
+            for j in (i + 1)..node_vec.len() {
+                if should_add_edge(&node_vec[i], &node_vec[j]) {
+                    local_edges.push((i, j));
+                }
             }
+            local_edges
+        })
+        .collect();
+
+    for (i, j) in edges_to_add {
+        let node_key = &keys[i];
+        let other_node_key = &keys[j];
+        if let Some(node_mut) = nodes.get_mut(node_key) {
+            node_mut.edges.push(other_node_key.clone());
+        }
+        if let Some(other_node_mut) = nodes.get_mut(other_node_key) {
+            other_node_mut.edges.push(node_key.clone());
         }
+    }
+}
 
-        for (node_key, other_node_key) in edges_to_add {
-            if let Some(node_mut) = nodes.get_mut(&node_key) {
-                node_mut.edges.push(other_node_key.clone());
-            }
-            if let Some(other_node_mut) = nodes.get_mut(&other_node_key) {
-                other_node_mut.edges.push(node_key.clone());
+
+// Finds connected regions of tracts whose food_insecurity_score is at or
+// above `threshold`, flood-filling over the existing edges adjacency.
+// Clusters are returned largest first.
+fn find_clusters(nodes: &HashMap<String, Node>, threshold: f64) -> Vec<Vec<String>> {
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut clusters: Vec<Vec<String>> = Vec::new();
+
+    for (key, node) in nodes {
+        if node.food_insecurity_score < threshold || visited.contains(key) {
+            continue;
+        }
+
+        let mut cluster = Vec::new();
+        let mut queue: VecDeque<String> = VecDeque::new();
+        queue.push_back(key.clone());
+        visited.insert(key.clone());
+
+        while let Some(current_key) = queue.pop_front() {
+            cluster.push(current_key.clone());
+
+            let current_node = match nodes.get(&current_key) {
+                Some(node) => node,
+                None => continue,
+            };
+
+            for neighbor_key in &current_node.edges {
+                if visited.contains(neighbor_key) {
+                    continue;
+                }
+                let neighbor_is_insecure = nodes
+                    .get(neighbor_key)
+                    .is_some_and(|neighbor| neighbor.food_insecurity_score >= threshold);
+
+                if neighbor_is_insecure {
+                    visited.insert(neighbor_key.clone());
+                    queue.push_back(neighbor_key.clone());
+                }
             }
         }
+
+        clusters.push(cluster);
     }
+
+    clusters.sort_by_key(|cluster| Reverse(cluster.len()));
+    clusters
 }
 
 
+fn degree_centrality(node: &Node, num_vertices: usize) -> f64 {
+    node.edges.len() as f64 / (num_vertices - 1) as f64
+}
+
 fn calculate_degree_centrality(nodes: &HashMap<String, Node>, num_vertices: usize) -> (f64, Option<&Node>) {
     let mut max_degree_centrality = 0.0;
     let mut max_food_insecurity_score = 0.0;
     let mut max_degree_centrality_node: Option<&Node> = None;
 
-    for (_, node) in nodes {
-        let degree_centrality = node.edges.len() as f64 / (num_vertices - 1) as f64;
+    for node in nodes.values() {
+        let degree_centrality = degree_centrality(node, num_vertices);
         if node.food_insecurity_score > max_food_insecurity_score {
             max_food_insecurity_score = node.food_insecurity_score;
             max_degree_centrality = degree_centrality;
@@ -155,6 +224,204 @@ fn calculate_degree_centrality(nodes: &HashMap<String, Node>, num_vertices: usiz
 }
 
 
+// Computes betweenness centrality over the unweighted edges graph using
+// Brandes' algorithm, so tracts that bridge otherwise separated regions can
+// be ranked instead of just finding the single most-connected tract.
+fn betweenness_centrality(nodes: &HashMap<String, Node>) -> HashMap<String, f64> {
+    let mut centrality: HashMap<String, f64> = nodes.keys().map(|key| (key.clone(), 0.0)).collect();
+
+    for source in nodes.keys() {
+        let mut stack: Vec<String> = Vec::new();
+        let mut predecessors: HashMap<String, Vec<String>> = HashMap::new();
+        let mut sigma: HashMap<String, f64> = nodes.keys().map(|key| (key.clone(), 0.0)).collect();
+        let mut distance: HashMap<String, i64> = nodes.keys().map(|key| (key.clone(), -1)).collect();
+
+        sigma.insert(source.clone(), 1.0);
+        distance.insert(source.clone(), 0);
+
+        let mut queue: VecDeque<String> = VecDeque::new();
+        queue.push_back(source.clone());
+
+        while let Some(v) = queue.pop_front() {
+            stack.push(v.clone());
+
+            let node = match nodes.get(&v) {
+                Some(node) => node,
+                None => continue,
+            };
+
+            for w in &node.edges {
+                if distance.get(w).copied().unwrap_or(-1) < 0 {
+                    distance.insert(w.clone(), distance[&v] + 1);
+                    queue.push_back(w.clone());
+                }
+
+                if distance.get(w).copied().unwrap_or(-1) == distance[&v] + 1 {
+                    let sigma_v = sigma[&v];
+                    *sigma.get_mut(w).unwrap() += sigma_v;
+                    predecessors.entry(w.clone()).or_default().push(v.clone());
+                }
+            }
+        }
+
+        let mut delta: HashMap<String, f64> = nodes.keys().map(|key| (key.clone(), 0.0)).collect();
+
+        while let Some(w) = stack.pop() {
+            if let Some(preds) = predecessors.get(&w) {
+                for v in preds {
+                    let contribution = (sigma[v] / sigma[&w]) * (1.0 + delta[&w]);
+                    *delta.get_mut(v).unwrap() += contribution;
+                }
+            }
+
+            if w != *source {
+                *centrality.get_mut(&w).unwrap() += delta[&w];
+            }
+        }
+    }
+
+    for value in centrality.values_mut() {
+        *value /= 2.0;
+    }
+
+    centrality
+}
+
+
+// A sortable attribute of a tract. Numeric string fields are parsed to f64
+// for comparison; DegreeCentrality is derived from edges.len() rather than
+// stored on the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Criterion {
+    FoodInsecurity,
+    DegreeCentrality,
+    PovertyRate,
+    SnapRate,
+    Population,
+}
+
+impl Criterion {
+    fn value(&self, node: &Node, num_vertices: usize) -> f64 {
+        match self {
+            Criterion::FoodInsecurity => node.food_insecurity_score,
+            Criterion::DegreeCentrality => degree_centrality(node, num_vertices),
+            Criterion::PovertyRate => node.poverty_rate.parse().unwrap_or(0.0),
+            Criterion::SnapRate => node.tract_snap.parse().unwrap_or(0.0),
+            Criterion::Population => node.pop_2010.parse().unwrap_or(0.0),
+        }
+    }
+}
+
+// Ranks tracts by applying `criteria` lexicographically: the first criterion
+// decides, ties are broken by the next, and so on, each ascending or
+// descending per its paired Ordering.
+fn rank_tracts<'a>(
+    nodes: &'a HashMap<String, Node>,
+    criteria: &[(Criterion, Ordering)],
+    num_vertices: usize,
+) -> Vec<&'a Node> {
+    let mut tracts: Vec<&Node> = nodes.values().collect();
+
+    tracts.sort_by(|a, b| {
+        for (criterion, direction) in criteria {
+            let a_value = criterion.value(a, num_vertices);
+            let b_value = criterion.value(b, num_vertices);
+            let ordering = a_value.partial_cmp(&b_value).unwrap_or(Ordering::Equal);
+            let ordering = if *direction == Ordering::Greater {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    });
+
+    tracts
+}
+
+
+// Wraps a cost so it can be ordered inside a BinaryHeap; food insecurity
+// scores are never NaN so plain total ordering is safe here.
+#[derive(PartialEq)]
+struct HeapCost(f64);
+
+impl Eq for HeapCost {}
+
+impl PartialOrd for HeapCost {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapCost {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// Finds the lowest-cost route between two census tracts, where the cost of
+// travelling between two tracts is the average of their food insecurity
+// scores. Returns the total cost and the path taken, or None if `to` is
+// unreachable from `from`.
+fn shortest_path(nodes: &HashMap<String, Node>, from: &str, to: &str) -> Option<(f64, Vec<String>)> {
+    let mut distances: HashMap<String, f64> = HashMap::new();
+    let mut predecessors: HashMap<String, String> = HashMap::new();
+    let mut heap: BinaryHeap<Reverse<(HeapCost, String)>> = BinaryHeap::new();
+
+    distances.insert(from.to_owned(), 0.0);
+    heap.push(Reverse((HeapCost(0.0), from.to_owned())));
+
+    while let Some(Reverse((HeapCost(cost), node_key))) = heap.pop() {
+        if node_key == to {
+            let mut path = vec![to.to_owned()];
+            let mut current = to.to_owned();
+            while let Some(prev) = predecessors.get(&current) {
+                path.push(prev.clone());
+                current = prev.clone();
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        if let Some(&best_known) = distances.get(&node_key) {
+            if cost > best_known {
+                continue;
+            }
+        }
+
+        let node = match nodes.get(&node_key) {
+            Some(node) => node,
+            None => continue,
+        };
+
+        for neighbor_key in &node.edges {
+            let neighbor = match nodes.get(neighbor_key) {
+                Some(neighbor) => neighbor,
+                None => continue,
+            };
+
+            let edge_weight = (node.food_insecurity_score + neighbor.food_insecurity_score) / 2.0;
+            let next_cost = cost + edge_weight;
+
+            let is_better = match distances.get(neighbor_key) {
+                Some(&known_cost) => next_cost < known_cost,
+                None => true,
+            };
+
+            if is_better {
+                distances.insert(neighbor_key.clone(), next_cost);
+                predecessors.insert(neighbor_key.clone(), node_key.clone());
+                heap.push(Reverse((HeapCost(next_cost), neighbor_key.clone())));
+            }
+        }
+    }
+
+    None
+}
 
 fn main() {
     println!("Starting the program...");
@@ -187,6 +454,44 @@ fn main() {
     } else {
         println!("No nodes found.");
     }
+
+    // Find the shortest food-insecurity-weighted path between two tracts
+    let mut tract_keys: Vec<&String> = nodes.keys().collect();
+    tract_keys.sort();
+    let mut tract_keys = tract_keys.into_iter();
+    if let (Some(from), Some(to)) = (tract_keys.next().cloned(), tract_keys.next().cloned()) {
+        match shortest_path(&nodes, &from, &to) {
+            Some((cost, path)) => println!("Shortest path from {} to {}: cost {}, path {:?}", from, to, cost, path),
+            None => println!("No path found from {} to {}", from, to),
+        }
+    }
+
+    // Find contiguous food-desert clusters
+    let clusters = find_clusters(&nodes, 50.0);
+    println!("Found {} food-desert clusters", clusters.len());
+    if let Some(largest_cluster) = clusters.first() {
+        println!("Largest cluster ({} tracts): {:?}", largest_cluster.len(), largest_cluster);
+    }
+
+    // Compute betweenness centrality to find bridge tracts
+    let betweenness = betweenness_centrality(&nodes);
+    if let Some((key, score)) = betweenness.iter().max_by(|a, b| a.1.partial_cmp(b.1).unwrap()) {
+        println!("Tract with the highest betweenness centrality: {} ({})", key, score);
+    }
+
+    // Rank tracts by food insecurity, breaking ties with poverty rate, SNAP
+    // rate, population, and finally degree centrality
+    let ranking_criteria = [
+        (Criterion::FoodInsecurity, Ordering::Greater),
+        (Criterion::PovertyRate, Ordering::Greater),
+        (Criterion::SnapRate, Ordering::Greater),
+        (Criterion::Population, Ordering::Greater),
+        (Criterion::DegreeCentrality, Ordering::Greater),
+    ];
+    let ranked_tracts = rank_tracts(&nodes, &ranking_criteria, num_vertices);
+    if let Some(top_tract) = ranked_tracts.first() {
+        println!("Top-ranked tract by food insecurity: {}", top_tract.census_tract);
+    }
 }
 
 
@@ -315,6 +620,168 @@ mod tests {
         assert_eq!(nodes.get("2").unwrap().edges[0], "1");
     }
 
+    #[test]
+    fn test_shortest_path() {
+        let mut nodes: HashMap<String, Node> = HashMap::new();
+
+        let node1 = Node {
+            census_tract: "1".to_owned(),
+            food_insecurity_score: 10.0,
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        let node2 = Node {
+            census_tract: "2".to_owned(),
+            food_insecurity_score: 20.0,
+            edges: vec!["1".to_owned(), "3".to_owned()],
+            ..Default::default()
+        };
+
+        let node3 = Node {
+            census_tract: "3".to_owned(),
+            food_insecurity_score: 30.0,
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        nodes.insert("1".to_owned(), node1);
+        nodes.insert("2".to_owned(), node2);
+        nodes.insert("3".to_owned(), node3);
+
+        let (cost, path) = shortest_path(&nodes, "1", "3").unwrap();
+        assert_eq!(path, vec!["1".to_owned(), "2".to_owned(), "3".to_owned()]);
+        assert_eq!(cost, 15.0 + 25.0);
+
+        assert!(shortest_path(&nodes, "3", "unknown").is_none());
+    }
+
+    #[test]
+    fn test_find_clusters() {
+        let mut nodes: HashMap<String, Node> = HashMap::new();
+
+        // Tracts 1 and 2 are insecure and connected, forming one cluster.
+        let node1 = Node {
+            census_tract: "1".to_owned(),
+            food_insecurity_score: 50.0,
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        let node2 = Node {
+            census_tract: "2".to_owned(),
+            food_insecurity_score: 40.0,
+            edges: vec!["1".to_owned(), "3".to_owned()],
+            ..Default::default()
+        };
+
+        // Tract 3 is below the threshold, so it breaks the flood-fill.
+        let node3 = Node {
+            census_tract: "3".to_owned(),
+            food_insecurity_score: 5.0,
+            edges: vec!["2".to_owned(), "4".to_owned()],
+            ..Default::default()
+        };
+
+        // Tract 4 is insecure but isolated from the 1/2 cluster.
+        let node4 = Node {
+            census_tract: "4".to_owned(),
+            food_insecurity_score: 45.0,
+            edges: vec!["3".to_owned()],
+            ..Default::default()
+        };
+
+        nodes.insert("1".to_owned(), node1);
+        nodes.insert("2".to_owned(), node2);
+        nodes.insert("3".to_owned(), node3);
+        nodes.insert("4".to_owned(), node4);
+
+        let mut clusters = find_clusters(&nodes, 30.0);
+        for cluster in clusters.iter_mut() {
+            cluster.sort();
+        }
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0], vec!["1".to_owned(), "2".to_owned()]);
+        assert_eq!(clusters[1], vec!["4".to_owned()]);
+    }
+
+    #[test]
+    fn test_betweenness_centrality() {
+        // A path graph 1 - 2 - 3: node 2 sits on every shortest path between
+        // the other two, so it should have the only nonzero centrality.
+        let mut nodes: HashMap<String, Node> = HashMap::new();
+
+        let node1 = Node {
+            census_tract: "1".to_owned(),
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        let node2 = Node {
+            census_tract: "2".to_owned(),
+            edges: vec!["1".to_owned(), "3".to_owned()],
+            ..Default::default()
+        };
+
+        let node3 = Node {
+            census_tract: "3".to_owned(),
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        nodes.insert("1".to_owned(), node1);
+        nodes.insert("2".to_owned(), node2);
+        nodes.insert("3".to_owned(), node3);
+
+        let centrality = betweenness_centrality(&nodes);
+
+        assert_eq!(centrality["1"], 0.0);
+        assert_eq!(centrality["3"], 0.0);
+        assert_eq!(centrality["2"], 1.0);
+    }
+
+    #[test]
+    fn test_rank_tracts() {
+        let mut nodes: HashMap<String, Node> = HashMap::new();
+
+        let node1 = Node {
+            census_tract: "1".to_owned(),
+            poverty_rate: "10.0".to_owned(),
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        let node2 = Node {
+            census_tract: "2".to_owned(),
+            poverty_rate: "25.0".to_owned(),
+            edges: vec!["1".to_owned(), "3".to_owned()],
+            ..Default::default()
+        };
+
+        let node3 = Node {
+            census_tract: "3".to_owned(),
+            poverty_rate: "25.0".to_owned(),
+            edges: vec!["2".to_owned()],
+            ..Default::default()
+        };
+
+        nodes.insert("1".to_owned(), node1);
+        nodes.insert("2".to_owned(), node2);
+        nodes.insert("3".to_owned(), node3);
+
+        // Highest poverty rate first, ties broken by highest degree centrality.
+        let criteria = [
+            (Criterion::PovertyRate, Ordering::Greater),
+            (Criterion::DegreeCentrality, Ordering::Greater),
+        ];
+        let ranked = rank_tracts(&nodes, &criteria, nodes.len());
+
+        assert_eq!(ranked[0].census_tract, "2");
+        assert_eq!(ranked[1].census_tract, "3");
+        assert_eq!(ranked[2].census_tract, "1");
+    }
+
 }
 
 